@@ -0,0 +1,4 @@
+//! API clients for OpenAI/ChatGPT backend services
+
+pub mod token;
+pub mod usage;
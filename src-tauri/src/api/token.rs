@@ -0,0 +1,101 @@
+//! OpenAI OAuth token refresh for ChatGPT-authenticated accounts
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AuthData, StoredAccount};
+
+const TOKEN_ENDPOINT: &str = "https://auth.openai.com/oauth/token";
+const CODEX_CLIENT_ID: &str = "app_EMoamEEZ73f0CkXaXp7hrann";
+const REFRESH_SCOPE: &str = "openid profile email offline_access";
+
+/// Window before expiry in which we proactively refresh rather than wait for a 401
+pub const REFRESH_SKEW_SECONDS: i64 = 5 * 60;
+
+#[derive(Debug, Serialize)]
+struct RefreshTokenRequest<'a> {
+    grant_type: &'static str,
+    client_id: &'static str,
+    refresh_token: &'a str,
+    scope: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct RefreshTokenResponse {
+    id_token: String,
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+/// Exchange an account's stored refresh token for a new token set and update it in place.
+///
+/// On success, `account`'s `AuthData::ChatGPT` fields are updated with the new tokens; the
+/// caller is responsible for persisting the account afterwards. Returns an error with a clear
+/// message if the refresh token has been revoked or the account has no refresh token at all.
+pub async fn refresh_account_token(account: &mut StoredAccount) -> Result<()> {
+    let (refresh_token, account_id) = match &account.auth_data {
+        AuthData::ChatGPT {
+            refresh_token,
+            account_id,
+            ..
+        } => (refresh_token.clone(), account_id.clone()),
+        AuthData::ApiKey { .. } => {
+            bail!("Cannot refresh token for an API key account: {}", account.name)
+        }
+    };
+
+    if refresh_token.is_empty() {
+        bail!(
+            "Account '{}' has no refresh token; re-import it from auth.json",
+            account.name
+        );
+    }
+
+    let request = RefreshTokenRequest {
+        grant_type: "refresh_token",
+        client_id: CODEX_CLIENT_ID,
+        refresh_token: &refresh_token,
+        scope: REFRESH_SCOPE,
+    };
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(TOKEN_ENDPOINT)
+        .json(&request)
+        .send()
+        .await
+        .context("Failed to send token refresh request")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        if status.as_u16() == 400 || status.as_u16() == 401 {
+            bail!(
+                "Refresh token for account '{}' has been revoked; re-import it from auth.json",
+                account.name
+            );
+        }
+        bail!("Token refresh failed with status {status}: {body}");
+    }
+
+    let refreshed: RefreshTokenResponse = response
+        .json()
+        .await
+        .context("Failed to parse token refresh response")?;
+
+    account.auth_data = AuthData::ChatGPT {
+        id_token: refreshed.id_token,
+        access_token: refreshed.access_token,
+        refresh_token: refreshed.refresh_token.unwrap_or(refresh_token),
+        account_id,
+    };
+
+    Ok(())
+}
+
+/// Whether a token expiring at `exp` (unix seconds) is due for a refresh now
+pub fn is_expiring_soon(exp: i64) -> bool {
+    exp - Utc::now().timestamp() <= REFRESH_SKEW_SECONDS
+}
@@ -1,6 +1,12 @@
 //! Usage API client for fetching rate limits and credits
 
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
 use anyhow::{Context, Result};
+use chrono::Utc;
+use once_cell::sync::Lazy;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, USER_AGENT};
 
 use crate::types::{
@@ -9,15 +15,39 @@ use crate::types::{
 };
 
 const CHATGPT_BACKEND_API: &str = "https://chatgpt.com/backend-api";
+const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
+
+/// How long a fetched `UsageInfo` is considered fresh enough to serve from cache
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Fallback cooldown applied to a failed fetch when neither a `Retry-After` header nor a known
+/// rate-limit window reset is available
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+struct CachedUsage {
+    info: UsageInfo,
+    fetched_at: Instant,
+    cooldown_until: Option<Instant>,
+}
+
+static USAGE_CACHE: Lazy<Mutex<HashMap<String, CachedUsage>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Get usage information for an account, serving a cached value when it is still fresh or the
+/// account is in a rate-limit cooldown. Pass `force` to always hit the backend.
+pub async fn get_account_usage(account: &StoredAccount, force: bool) -> Result<UsageInfo> {
+    if !force {
+        if let Some(cached) = cached_usage(&account.id) {
+            return Ok(cached);
+        }
+    }
 
-/// Get usage information for an account
-pub async fn get_account_usage(account: &StoredAccount) -> Result<UsageInfo> {
     println!("[Usage] Fetching usage for account: {}", account.name);
 
     match &account.auth_data {
         AuthData::ApiKey { .. } => {
             println!("[Usage] API key accounts don't support usage info");
-            Ok(UsageInfo {
+            let info = UsageInfo {
                 account_id: account.id.clone(),
                 plan_type: Some("api_key".to_string()),
                 primary_used_percent: None,
@@ -30,31 +60,114 @@ pub async fn get_account_usage(account: &StoredAccount) -> Result<UsageInfo> {
                 unlimited_credits: None,
                 credits_balance: None,
                 error: Some("Usage info not available for API key accounts".to_string()),
-            })
+            };
+            store_cached_usage(&account.id, info.clone());
+            Ok(info)
         }
         AuthData::ChatGPT {
             access_token,
             account_id,
             ..
         } => {
-            get_usage_with_chatgpt_token(
+            let (info, retry_after) = get_usage_with_chatgpt_token(
                 &account.id,
                 &account.name,
                 access_token,
                 account_id.as_deref(),
             )
-            .await
+            .await?;
+
+            if info.error.is_some() {
+                // A failed fetch — rate-limited or any other non-success status — must never
+                // clobber a good cached value. Prefer the backend's explicit Retry-After, falling
+                // back to the soonest rate-limit window reset recorded in the last good fetch, or
+                // a flat default if neither is known.
+                let cooldown = retry_after
+                    .or_else(|| reset_cooldown(&account.id))
+                    .unwrap_or(DEFAULT_COOLDOWN);
+                return Ok(apply_cooldown(&account.id, cooldown, info));
+            }
+
+            store_cached_usage(&account.id, info.clone());
+            Ok(info)
         }
     }
 }
 
+/// Return a cached usage value if it is within the TTL or the account is still in cooldown
+fn cached_usage(account_id: &str) -> Option<UsageInfo> {
+    let cache = USAGE_CACHE.lock().unwrap();
+    let cached = cache.get(account_id)?;
+
+    let in_cooldown = cached.cooldown_until.is_some_and(|until| Instant::now() < until);
+    let is_fresh = cached.fetched_at.elapsed() < CACHE_TTL;
+
+    if in_cooldown || is_fresh {
+        Some(cached.info.clone())
+    } else {
+        None
+    }
+}
+
+fn store_cached_usage(account_id: &str, info: UsageInfo) {
+    USAGE_CACHE.lock().unwrap().insert(
+        account_id.to_string(),
+        CachedUsage {
+            info,
+            fetched_at: Instant::now(),
+            cooldown_until: None,
+        },
+    );
+}
+
+/// Put an account into a rate-limit cooldown without losing its last successfully fetched
+/// `UsageInfo`. Returns the value now being served: the prior cached value if one exists, or
+/// `fallback` (typically the fresh error placeholder) if this is the first fetch for the account.
+fn apply_cooldown(account_id: &str, cooldown: Duration, fallback: UsageInfo) -> UsageInfo {
+    let mut cache = USAGE_CACHE.lock().unwrap();
+    match cache.get_mut(account_id) {
+        Some(cached) => {
+            cached.cooldown_until = Some(Instant::now() + cooldown);
+            cached.info.clone()
+        }
+        None => {
+            cache.insert(
+                account_id.to_string(),
+                CachedUsage {
+                    info: fallback.clone(),
+                    fetched_at: Instant::now(),
+                    cooldown_until: Some(Instant::now() + cooldown),
+                },
+            );
+            fallback
+        }
+    }
+}
+
+/// Duration until the soonest rate-limit window reset recorded in an account's last good cached
+/// usage, used as a cooldown fallback when the backend doesn't send a `Retry-After` header
+fn reset_cooldown(account_id: &str) -> Option<Duration> {
+    let cache = USAGE_CACHE.lock().unwrap();
+    let info = &cache.get(account_id)?.info;
+    let now = Utc::now();
+    [info.primary_resets_at, info.secondary_resets_at]
+        .into_iter()
+        .flatten()
+        .filter(|reset_at| *reset_at > now)
+        .min()
+        .map(|reset_at| (reset_at - now).to_std().unwrap_or(DEFAULT_COOLDOWN))
+}
+
 /// Get usage with ChatGPT access token
+///
+/// Returns the fetched (or error-placeholder) `UsageInfo` alongside the `Retry-After` duration
+/// the backend sent, if any. The caller decides how to translate a failure into a cooldown.
 async fn get_usage_with_chatgpt_token(
     account_id: &str,
     account_name: &str,
     access_token: &str,
     chatgpt_account_id: Option<&str>,
-) -> Result<UsageInfo> {
+) -> Result<(UsageInfo, Option<Duration>)> {
     let client = reqwest::Client::new();
 
     let mut headers = HeaderMap::new();
@@ -88,11 +201,12 @@ async fn get_usage_with_chatgpt_token(
     println!("[Usage] Response status: {status}");
 
     if !status.is_success() {
+        let retry_after = retry_after(response.headers());
         let body = response.text().await.unwrap_or_default();
         println!("[Usage] Error response: {body}");
-        return Ok(UsageInfo::error(
-            account_id.to_string(),
-            format!("API error: {status}"),
+        return Ok((
+            UsageInfo::error(account_id.to_string(), format!("API error: {status}")),
+            retry_after,
         ));
     }
 
@@ -116,7 +230,13 @@ async fn get_usage_with_chatgpt_token(
         account_name, usage.primary_used_percent, usage.plan_type
     );
 
-    Ok(usage)
+    Ok((usage, None))
+}
+
+/// Parse a `Retry-After` header as a number of seconds to wait before trying again
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds: u64 = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.parse().ok()?;
+    Some(Duration::from_secs(seconds))
 }
 
 /// Convert API response to UsageInfo
@@ -159,14 +279,93 @@ fn extract_credits(credits: Option<CreditStatusDetails>) -> Option<CreditStatusD
     credits
 }
 
+/// Verify that an account's stored credential still authenticates, for either auth mode.
+///
+/// ChatGPT accounts get a real check from the WHAM usage request (it fails if the token is
+/// dead), made directly so that neither a cached value nor a cooldown can mask the result.
+/// [`get_account_usage`] reports a static "not available" error for API-key accounts; this
+/// instead calls a lightweight authenticated endpoint to find out.
+pub async fn verify_account(account: &StoredAccount) -> Result<UsageInfo> {
+    match &account.auth_data {
+        AuthData::ApiKey { key } => verify_api_key(&account.id, key).await,
+        AuthData::ChatGPT {
+            access_token,
+            account_id,
+            ..
+        } => {
+            let (info, _retry_after) = get_usage_with_chatgpt_token(
+                &account.id,
+                &account.name,
+                access_token,
+                account_id.as_deref(),
+            )
+            .await?;
+            Ok(info)
+        }
+    }
+}
+
+/// Call a lightweight authenticated OpenAI endpoint to check whether an API key still works
+async fn verify_api_key(account_id: &str, key: &str) -> Result<UsageInfo> {
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{OPENAI_API_BASE}/models"))
+        .header(AUTHORIZATION, format!("Bearer {key}"))
+        .send()
+        .await
+        .context("Failed to send API key verification request")?;
+
+    let status = response.status();
+    let organization = response
+        .headers()
+        .get("openai-organization")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    let project = response
+        .headers()
+        .get("openai-project")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+
+    let error = if status == reqwest::StatusCode::UNAUTHORIZED {
+        Some("API key is invalid or has been revoked".to_string())
+    } else if !status.is_success() {
+        Some(format!("API error: {status}"))
+    } else {
+        None
+    };
+
+    let plan_type = match (&organization, &project) {
+        (Some(org), Some(project)) => format!("api_key ({org}/{project})"),
+        (Some(org), None) => format!("api_key ({org})"),
+        _ => "api_key".to_string(),
+    };
+
+    Ok(UsageInfo {
+        account_id: account_id.to_string(),
+        plan_type: Some(plan_type),
+        primary_used_percent: None,
+        primary_window_minutes: None,
+        primary_resets_at: None,
+        secondary_used_percent: None,
+        secondary_window_minutes: None,
+        secondary_resets_at: None,
+        has_credits: None,
+        unlimited_credits: None,
+        credits_balance: None,
+        error,
+    })
+}
+
 /// Refresh all account usage in parallel
-pub async fn refresh_all_usage(accounts: &[StoredAccount]) -> Vec<UsageInfo> {
+pub async fn refresh_all_usage(accounts: &[StoredAccount], force: bool) -> Vec<UsageInfo> {
     println!("[Usage] Refreshing usage for {} accounts", accounts.len());
 
     let futures: Vec<_> = accounts
         .iter()
         .map(|account| async move {
-            match get_account_usage(account).await {
+            match get_account_usage(account, force).await {
                 Ok(info) => info,
                 Err(e) => {
                     println!("[Usage] Error for {}: {}", account.name, e);
@@ -0,0 +1,267 @@
+//! Account store persistence, with optional password-based encryption at rest
+//!
+//! By default the store is a plain JSON file. Once a master password is set via
+//! [`set_master_password`], it is sealed as an [`EncryptedEnvelope`]: a random salt and
+//! Argon2id parameters used to derive a 256-bit key from the password, and an XChaCha20-Poly1305
+//! ciphertext of the serialized [`AccountStore`] under a fresh random nonce. The derived key is
+//! kept in memory for the session after a successful [`unlock_vault`] or [`set_master_password`]
+//! call; it is never written to disk.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use once_cell::sync::Lazy;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::types::StoredAccount;
+
+pub(crate) const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+pub(crate) const KEY_LEN: usize = 32;
+pub(crate) const ENVELOPE_VERSION: u8 = 1;
+
+/// In-memory vault key for the current session, set by `unlock_vault`/`set_master_password`
+static VAULT_KEY: Lazy<Mutex<Option<[u8; KEY_LEN]>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AccountStore {
+    pub accounts: Vec<StoredAccount>,
+    pub active_account_id: Option<String>,
+}
+
+/// Argon2id parameters used to derive a vault key. Stored alongside the ciphertext (not just
+/// assumed from the crate's current defaults) so that a future change to [`KdfParams::CURRENT`]
+/// never breaks decryption of a vault or backup sealed under older parameters.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct KdfParams {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl KdfParams {
+    /// Parameters used to seal new vaults and backups going forward
+    pub(crate) const CURRENT: Self = Self {
+        m_cost: 19_456, // ~19 MiB, OWASP-recommended minimum for Argon2id
+        t_cost: 2,
+        p_cost: 1,
+    };
+}
+
+/// On-disk envelope for an encrypted account store
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct EncryptedEnvelope {
+    version: u8,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    kdf_params: KdfParams,
+}
+
+fn get_store_path() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir().context("Could not find config directory")?;
+    let dir = config_dir.join("codex-switcher");
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create config dir: {}", dir.display()))?;
+    Ok(dir.join("accounts.json"))
+}
+
+fn write_store_file(content: &str) -> Result<()> {
+    let path = get_store_path()?;
+    fs::write(&path, content)
+        .with_context(|| format!("Failed to write account store: {}", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn derive_key(password: &str, salt: &[u8], params: KdfParams) -> Result<[u8; KEY_LEN]> {
+    let argon2_params = argon2::Params::new(params.m_cost, params.t_cost, params.p_cost, Some(KEY_LEN))
+        .map_err(|e| anyhow::anyhow!("Invalid KDF params: {e}"))?;
+    let argon2 = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, argon2_params);
+
+    let mut key = [0u8; KEY_LEN];
+    argon2
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Failed to derive key from password: {e}"))?;
+    Ok(key)
+}
+
+pub(crate) fn seal(
+    key: &[u8; KEY_LEN],
+    salt: &[u8; SALT_LEN],
+    params: KdfParams,
+    plaintext: &[u8],
+) -> Result<EncryptedEnvelope> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce_bytes), plaintext)
+        .map_err(|e| anyhow::anyhow!("Failed to encrypt account store: {e}"))?;
+
+    let b64 = base64::engine::general_purpose::STANDARD;
+    Ok(EncryptedEnvelope {
+        version: ENVELOPE_VERSION,
+        salt: b64.encode(salt),
+        nonce: b64.encode(nonce_bytes),
+        ciphertext: b64.encode(ciphertext),
+        kdf_params: params,
+    })
+}
+
+pub(crate) fn open_envelope(key: &[u8; KEY_LEN], envelope: &EncryptedEnvelope) -> Result<Vec<u8>> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let nonce_bytes = b64.decode(&envelope.nonce).context("Invalid nonce encoding")?;
+    let ciphertext = b64
+        .decode(&envelope.ciphertext)
+        .context("Invalid ciphertext encoding")?;
+
+    let cipher = XChaCha20Poly1305::new(key.into());
+    cipher
+        .decrypt(XNonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| anyhow::anyhow!("Incorrect master password"))
+}
+
+pub(crate) fn decode_salt(envelope: &EncryptedEnvelope) -> Result<[u8; SALT_LEN]> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(&envelope.salt)
+        .context("Invalid salt encoding")?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Unexpected salt length in account store"))
+}
+
+/// The Argon2id parameters an envelope was sealed with
+pub(crate) fn params_of(envelope: &EncryptedEnvelope) -> KdfParams {
+    envelope.kdf_params
+}
+
+/// The envelope format version an envelope was sealed with
+pub(crate) fn version_of(envelope: &EncryptedEnvelope) -> u8 {
+    envelope.version
+}
+
+fn read_envelope() -> Result<Option<EncryptedEnvelope>> {
+    let path = get_store_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read account store: {}", path.display()))?;
+    Ok(serde_json::from_str::<EncryptedEnvelope>(&content).ok())
+}
+
+/// Whether the on-disk account store is using the encrypted vault format
+pub fn is_vault_encrypted() -> Result<bool> {
+    Ok(read_envelope()?.is_some())
+}
+
+/// Unlock an encrypted vault with the master password, keeping the derived key in memory
+/// for the rest of the session. Fails if the password is wrong or the store isn't encrypted.
+pub fn unlock_vault(password: &str) -> Result<()> {
+    let envelope = read_envelope()?.context("Account store is not encrypted")?;
+    let salt = decode_salt(&envelope)?;
+    let key = derive_key(password, &salt, params_of(&envelope))?;
+
+    // Verify the password by attempting a decrypt before committing to it
+    open_envelope(&key, &envelope)?;
+
+    *VAULT_KEY.lock().unwrap() = Some(key);
+    Ok(())
+}
+
+/// Enable or change the master password, re-encrypting (or migrating) the account store.
+///
+/// Pass `old` as `None` to seal a plaintext store for the first time; an existing encrypted
+/// store requires the current password to re-encrypt under the new one.
+pub fn set_master_password(old: Option<&str>, new: &str) -> Result<()> {
+    let store = match old {
+        Some(old_password) => {
+            unlock_vault(old_password)?;
+            load_accounts()?
+        }
+        None => {
+            if is_vault_encrypted()? {
+                bail!("Account store is already encrypted; provide the current password");
+            }
+            load_accounts()?
+        }
+    };
+
+    let mut salt = [0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let key = derive_key(new, &salt, KdfParams::CURRENT)?;
+
+    let plaintext = serde_json::to_vec(&store).context("Failed to serialize account store")?;
+    let envelope = seal(&key, &salt, KdfParams::CURRENT, &plaintext)?;
+    write_store_file(&serde_json::to_string_pretty(&envelope)?)?;
+
+    *VAULT_KEY.lock().unwrap() = Some(key);
+    Ok(())
+}
+
+/// Load the account store, transparently decrypting it if the vault is encrypted
+pub fn load_accounts() -> Result<AccountStore> {
+    let path = get_store_path()?;
+    if !path.exists() {
+        return Ok(AccountStore::default());
+    }
+
+    if let Some(envelope) = read_envelope()? {
+        let key = VAULT_KEY
+            .lock()
+            .unwrap()
+            .context("Vault is locked; call unlock_vault with the master password first")?;
+        let plaintext = open_envelope(&key, &envelope)?;
+        return serde_json::from_slice(&plaintext)
+            .context("Failed to parse decrypted account store");
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read account store: {}", path.display()))?;
+    serde_json::from_str(&content).context("Failed to parse account store")
+}
+
+/// Save the account store, transparently re-encrypting it if the vault is unlocked
+pub fn save_accounts(store: &AccountStore) -> Result<()> {
+    let key = *VAULT_KEY.lock().unwrap();
+    let Some(key) = key else {
+        let content =
+            serde_json::to_string_pretty(store).context("Failed to serialize account store")?;
+        return write_store_file(&content);
+    };
+
+    let (salt, params) = match read_envelope()? {
+        // The in-memory key was derived under this envelope's params, so re-seal with the same
+        // ones rather than `KdfParams::CURRENT` — they may no longer match.
+        Some(envelope) => (decode_salt(&envelope)?, params_of(&envelope)),
+        None => {
+            let mut salt = [0u8; SALT_LEN];
+            rand::thread_rng().fill_bytes(&mut salt);
+            (salt, KdfParams::CURRENT)
+        }
+    };
+
+    let plaintext = serde_json::to_vec(store).context("Failed to serialize account store")?;
+    let envelope = seal(&key, &salt, params, &plaintext)?;
+    write_store_file(&serde_json::to_string_pretty(&envelope)?)
+}
+
+/// Get a single account by id
+pub fn get_account(id: &str) -> Result<Option<StoredAccount>> {
+    let store = load_accounts()?;
+    Ok(store.accounts.into_iter().find(|a| a.id == id))
+}
@@ -5,9 +5,23 @@ use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 use chrono::Utc;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
 
+use crate::api::token::{is_expiring_soon, refresh_account_token};
+use crate::auth::storage::{self, load_accounts, save_accounts, AccountStore};
 use crate::types::{AuthData, AuthDotJson, StoredAccount, TokenData};
 
+/// What to do when an imported account collides with one already in the store (matched by
+/// id or, failing that, email)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflictPolicy {
+    Skip,
+    Overwrite,
+    KeepBoth,
+}
+
 /// Get the official Codex home directory
 pub fn get_codex_home() -> Result<PathBuf> {
     // Check for CODEX_HOME environment variable first
@@ -25,7 +39,26 @@ pub fn get_codex_auth_file() -> Result<PathBuf> {
 }
 
 /// Switch to a specific account by writing its credentials to ~/.codex/auth.json
-pub fn switch_to_account(account: &StoredAccount) -> Result<()> {
+///
+/// If the account uses ChatGPT token auth and its `id_token` is within
+/// [`crate::api::token::REFRESH_SKEW_SECONDS`] of expiring, the token is refreshed first so
+/// `auth.json` never ends up holding a token that is already dead on arrival. OpenAI rotates
+/// the refresh token on each use, so a refreshed account is immediately persisted back to the
+/// store — otherwise the store would keep the now-consumed refresh token and the next switch
+/// would fail.
+pub async fn switch_to_account(account: &mut StoredAccount) -> Result<()> {
+    if let AuthData::ChatGPT { id_token, .. } = &account.auth_data {
+        if let (_, _, Some(exp)) = parse_id_token_claims(id_token) {
+            if is_expiring_soon(exp) {
+                refresh_account_token(account)
+                    .await
+                    .context("Failed to refresh expiring ChatGPT token")?;
+                account.last_refresh = Some(Utc::now());
+                persist_account(account)?;
+            }
+        }
+    }
+
     let codex_home = get_codex_home()?;
 
     // Ensure the codex home directory exists
@@ -52,6 +85,16 @@ pub fn switch_to_account(account: &StoredAccount) -> Result<()> {
     Ok(())
 }
 
+/// Write an updated account back into the persistent store, replacing the entry with the same id
+fn persist_account(account: &StoredAccount) -> Result<()> {
+    let mut store = load_accounts()?;
+    match store.accounts.iter_mut().find(|a| a.id == account.id) {
+        Some(existing) => *existing = account.clone(),
+        None => store.accounts.push(account.clone()),
+    }
+    save_accounts(&store)
+}
+
 /// Create an AuthDotJson structure from a StoredAccount
 fn create_auth_json(account: &StoredAccount) -> Result<AuthDotJson> {
     match &account.auth_data {
@@ -91,7 +134,14 @@ pub fn import_from_auth_json(path: &str, account_name: String) -> Result<StoredA
         Ok(StoredAccount::new_api_key(account_name, api_key))
     } else if let Some(tokens) = auth.tokens {
         // Try to extract email and plan from id_token
-        let (email, plan_type) = parse_id_token_claims(&tokens.id_token);
+        let (email, plan_type, _exp) = parse_id_token_claims(&tokens.id_token);
+
+        if tokens.refresh_token.is_empty() {
+            println!(
+                "[Auth] Warning: '{account_name}' has no refresh token; it was likely imported \
+                 without the offline_access scope and will stop working once its access token expires"
+            );
+        }
 
         Ok(StoredAccount::new_chatgpt(
             account_name,
@@ -108,22 +158,24 @@ pub fn import_from_auth_json(path: &str, account_name: String) -> Result<StoredA
 }
 
 /// Parse claims from a JWT ID token (without validation)
-fn parse_id_token_claims(id_token: &str) -> (Option<String>, Option<String>) {
+///
+/// Returns `(email, plan_type, expiry)`, where `expiry` is the `exp` claim in unix seconds.
+fn parse_id_token_claims(id_token: &str) -> (Option<String>, Option<String>, Option<i64>) {
     let parts: Vec<&str> = id_token.split('.').collect();
     if parts.len() != 3 {
-        return (None, None);
+        return (None, None, None);
     }
 
     // Decode the payload (second part)
     let payload =
         match base64::Engine::decode(&base64::engine::general_purpose::URL_SAFE_NO_PAD, parts[1]) {
             Ok(bytes) => bytes,
-            Err(_) => return (None, None),
+            Err(_) => return (None, None, None),
         };
 
     let json: serde_json::Value = match serde_json::from_slice(&payload) {
         Ok(v) => v,
-        Err(_) => return (None, None),
+        Err(_) => return (None, None, None),
     };
 
     let email = json.get("email").and_then(|v| v.as_str()).map(String::from);
@@ -135,7 +187,9 @@ fn parse_id_token_claims(id_token: &str) -> (Option<String>, Option<String>) {
         .and_then(|v| v.as_str())
         .map(String::from);
 
-    (email, plan_type)
+    let exp = json.get("exp").and_then(|v| v.as_i64());
+
+    (email, plan_type, exp)
 }
 
 /// Read the current auth.json file if it exists
@@ -162,3 +216,91 @@ pub fn has_active_login() -> Result<bool> {
         None => Ok(false),
     }
 }
+
+/// Export every stored account (ChatGPT token sets and API keys alike) into a single
+/// password-protected backup file, sealed with the same Argon2id + XChaCha20-Poly1305
+/// primitives as the at-rest vault.
+pub fn export_accounts(path: &str, password: &str) -> Result<()> {
+    let store = load_accounts()?;
+    let plaintext = serde_json::to_vec(&store).context("Failed to serialize account store")?;
+
+    let mut salt = [0u8; storage::SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let key = storage::derive_key(password, &salt, storage::KdfParams::CURRENT)?;
+    let envelope = storage::seal(&key, &salt, storage::KdfParams::CURRENT, &plaintext)?;
+
+    let content =
+        serde_json::to_string_pretty(&envelope).context("Failed to serialize backup file")?;
+    fs::write(path, content).with_context(|| format!("Failed to write backup file: {path}"))?;
+
+    Ok(())
+}
+
+/// Import accounts from a backup file created by [`export_accounts`], merging them into the
+/// current store according to `conflict_policy` when an account id or email already exists.
+/// Returns the number of accounts added or updated.
+pub fn import_accounts(
+    path: &str,
+    password: &str,
+    conflict_policy: ImportConflictPolicy,
+) -> Result<usize> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read backup file: {path}"))?;
+    let envelope: storage::EncryptedEnvelope =
+        serde_json::from_str(&content).context("Not a valid codex-switcher backup file")?;
+
+    let version = storage::version_of(&envelope);
+    if version != storage::ENVELOPE_VERSION {
+        anyhow::bail!(
+            "Backup file is format version {version}, but this version of codex-switcher only \
+             understands version {}; update codex-switcher and try again",
+            storage::ENVELOPE_VERSION
+        );
+    }
+
+    let salt = storage::decode_salt(&envelope)?;
+    let key = storage::derive_key(password, &salt, storage::params_of(&envelope))?;
+    let plaintext = storage::open_envelope(&key, &envelope)?;
+
+    let imported: AccountStore =
+        serde_json::from_slice(&plaintext).context("Failed to parse backup contents")?;
+
+    let mut store = load_accounts()?;
+    let mut applied = 0usize;
+
+    for account in imported.accounts {
+        let existing = store
+            .accounts
+            .iter()
+            .position(|a| a.id == account.id)
+            .or_else(|| {
+                account.email.as_ref().and_then(|email| {
+                    store.accounts.iter().position(|a| a.email.as_ref() == Some(email))
+                })
+            });
+
+        match existing {
+            None => {
+                store.accounts.push(account);
+                applied += 1;
+            }
+            Some(idx) => match conflict_policy {
+                ImportConflictPolicy::Skip => {}
+                ImportConflictPolicy::Overwrite => {
+                    store.accounts[idx] = account;
+                    applied += 1;
+                }
+                ImportConflictPolicy::KeepBoth => {
+                    let mut copy = account;
+                    copy.id = format!("{}-imported-{}", copy.id, Utc::now().timestamp());
+                    copy.name = format!("{} (imported)", copy.name);
+                    store.accounts.push(copy);
+                    applied += 1;
+                }
+            },
+        }
+    }
+
+    save_accounts(&store)?;
+    Ok(applied)
+}
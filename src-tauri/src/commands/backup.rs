@@ -0,0 +1,20 @@
+//! Account backup/restore Tauri commands
+
+use crate::auth::switcher::{self, ImportConflictPolicy};
+
+/// Export the full account store to a password-protected backup file
+#[tauri::command]
+pub fn export_accounts(path: String, password: String) -> Result<(), String> {
+    switcher::export_accounts(&path, &password).map_err(|e| e.to_string())
+}
+
+/// Import accounts from a backup file, merging with the current store. Returns the number
+/// of accounts added or updated.
+#[tauri::command]
+pub fn import_accounts(
+    path: String,
+    password: String,
+    conflict_policy: ImportConflictPolicy,
+) -> Result<usize, String> {
+    switcher::import_accounts(&path, &password, conflict_policy).map_err(|e| e.to_string())
+}
@@ -0,0 +1,5 @@
+//! Tauri command handlers
+
+pub mod backup;
+pub mod usage;
+pub mod vault;
@@ -11,12 +11,27 @@ pub async fn get_usage(account_id: String) -> Result<UsageInfo, String> {
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Account not found: {account_id}"))?;
 
-    get_account_usage(&account).await.map_err(|e| e.to_string())
+    get_account_usage(&account, false)
+        .await
+        .map_err(|e| e.to_string())
 }
 
-/// Refresh usage info for all accounts
+/// Refresh usage info for all accounts. Pass `force` to bypass the usage cache and any
+/// active rate-limit cooldown.
 #[tauri::command]
-pub async fn refresh_all_accounts_usage() -> Result<Vec<UsageInfo>, String> {
+pub async fn refresh_all_accounts_usage(force: bool) -> Result<Vec<UsageInfo>, String> {
     let store = load_accounts().map_err(|e| e.to_string())?;
-    Ok(refresh_all_usage(&store.accounts).await)
+    Ok(refresh_all_usage(&store.accounts, force).await)
+}
+
+/// Check whether an account's stored credential still authenticates, for either auth mode
+#[tauri::command]
+pub async fn verify_account(account_id: String) -> Result<UsageInfo, String> {
+    let account = get_account(&account_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Account not found: {account_id}"))?;
+
+    crate::api::usage::verify_account(&account)
+        .await
+        .map_err(|e| e.to_string())
 }
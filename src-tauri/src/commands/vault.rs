@@ -0,0 +1,21 @@
+//! Vault lock/unlock Tauri commands
+
+use crate::auth::storage;
+
+/// Unlock an encrypted account store with the master password
+#[tauri::command]
+pub fn unlock_vault(password: String) -> Result<(), String> {
+    storage::unlock_vault(&password).map_err(|e| e.to_string())
+}
+
+/// Set or change the master password, encrypting the account store if it is currently plaintext
+#[tauri::command]
+pub fn set_master_password(old: Option<String>, new: String) -> Result<(), String> {
+    storage::set_master_password(old.as_deref(), &new).map_err(|e| e.to_string())
+}
+
+/// Whether the account store on disk is currently encrypted
+#[tauri::command]
+pub fn is_vault_encrypted() -> Result<bool, String> {
+    storage::is_vault_encrypted().map_err(|e| e.to_string())
+}
@@ -0,0 +1,151 @@
+//! Shared data types for accounts, the official Codex auth.json format, and usage info
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Credential material for a stored account: either a plain API key or a ChatGPT token set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthData {
+    ApiKey {
+        key: String,
+    },
+    ChatGPT {
+        id_token: String,
+        access_token: String,
+        refresh_token: String,
+        account_id: Option<String>,
+    },
+}
+
+/// An account managed by the switcher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredAccount {
+    pub id: String,
+    pub name: String,
+    pub email: Option<String>,
+    pub plan_type: Option<String>,
+    pub auth_data: AuthData,
+    /// When this account's credentials were last refreshed; `None` until the first refresh
+    pub last_refresh: Option<DateTime<Utc>>,
+}
+
+impl StoredAccount {
+    pub fn new_api_key(name: String, key: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            email: None,
+            plan_type: None,
+            auth_data: AuthData::ApiKey { key },
+            last_refresh: None,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_chatgpt(
+        name: String,
+        email: Option<String>,
+        plan_type: Option<String>,
+        id_token: String,
+        access_token: String,
+        refresh_token: String,
+        account_id: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            email,
+            plan_type,
+            auth_data: AuthData::ChatGPT {
+                id_token,
+                access_token,
+                refresh_token,
+                account_id,
+            },
+            last_refresh: None,
+        }
+    }
+}
+
+/// Token data as stored in the official `~/.codex/auth.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenData {
+    pub id_token: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    pub account_id: Option<String>,
+}
+
+/// The shape of the official Codex `auth.json` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthDotJson {
+    pub openai_api_key: Option<String>,
+    pub tokens: Option<TokenData>,
+    pub last_refresh: Option<DateTime<Utc>>,
+}
+
+/// Usage and rate limit info for an account, as surfaced to the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageInfo {
+    pub account_id: String,
+    pub plan_type: Option<String>,
+    pub primary_used_percent: Option<f64>,
+    pub primary_window_minutes: Option<i64>,
+    pub primary_resets_at: Option<DateTime<Utc>>,
+    pub secondary_used_percent: Option<f64>,
+    pub secondary_window_minutes: Option<i64>,
+    pub secondary_resets_at: Option<DateTime<Utc>>,
+    pub has_credits: Option<bool>,
+    pub unlimited_credits: Option<bool>,
+    pub credits_balance: Option<f64>,
+    pub error: Option<String>,
+}
+
+impl UsageInfo {
+    /// Build a placeholder `UsageInfo` carrying only an error message
+    pub fn error(account_id: String, error: String) -> Self {
+        Self {
+            account_id,
+            plan_type: None,
+            primary_used_percent: None,
+            primary_window_minutes: None,
+            primary_resets_at: None,
+            secondary_used_percent: None,
+            secondary_window_minutes: None,
+            secondary_resets_at: None,
+            has_credits: None,
+            unlimited_credits: None,
+            credits_balance: None,
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitStatusPayload {
+    pub plan_type: String,
+    pub rate_limit: Option<RateLimitDetails>,
+    pub credits: Option<CreditStatusDetails>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitDetails {
+    pub primary_window: Option<RateLimitWindow>,
+    pub secondary_window: Option<RateLimitWindow>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitWindow {
+    pub used_percent: f64,
+    pub limit_window_seconds: Option<u32>,
+    pub reset_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreditStatusDetails {
+    pub has_credits: bool,
+    pub unlimited: bool,
+    pub balance: Option<f64>,
+}